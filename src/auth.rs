@@ -0,0 +1,93 @@
+use std::{collections::HashSet, env};
+
+use axum::{extract::Request, http::header, middleware::Next, response::Response};
+
+use crate::error::AppError;
+
+// ================== Bearer Token Auth ====================
+// Gates the resume-handling endpoints behind a shared secret. Configured via
+// `AUTH_TOKENS` (comma-separated) so each client can be issued its own
+// token; health checks are left off this layer so uptime probes don't need
+// a credential.
+fn allowed_tokens() -> HashSet<String> {
+    env::var("AUTH_TOKENS")
+        .map(|v| {
+            v.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Picks a token for this process's own outbound requests (the `RunnerClient`
+// in `--runner` mode): a dedicated `RUNNER_TOKEN` if set, else the first
+// entry of `AUTH_TOKENS` so a single-token deployment needs no extra config.
+pub fn runner_token() -> Option<String> {
+    if let Ok(token) = env::var("RUNNER_TOKEN") {
+        return Some(token);
+    }
+    env::var("AUTH_TOKENS").ok().and_then(|v| {
+        v.split(',')
+            .map(|s| s.trim())
+            .find(|s| !s.is_empty())
+            .map(str::to_string)
+    })
+}
+
+// Pulled out of `require_bearer_token` so the auth decision itself (as
+// opposed to the axum plumbing around it) can be unit tested directly.
+fn is_authorized(tokens: &HashSet<String>, header_value: Option<&str>) -> bool {
+    // No tokens configured means auth is off (local/dev use).
+    if tokens.is_empty() {
+        return true;
+    }
+    header_value
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|t| tokens.contains(t))
+}
+
+pub async fn require_bearer_token(req: Request, next: Next) -> Result<Response, AppError> {
+    let header_value = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+
+    if is_authorized(&allowed_tokens(), header_value) {
+        Ok(next.run(req).await)
+    } else {
+        Err(AppError::Unauthorized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(values: &[&str]) -> HashSet<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn empty_token_set_allows_any_request() {
+        assert!(is_authorized(&tokens(&[]), None));
+        assert!(is_authorized(&tokens(&[]), Some("Bearer whatever")));
+    }
+
+    #[test]
+    fn missing_header_is_rejected_when_tokens_configured() {
+        assert!(!is_authorized(&tokens(&["secret"]), None));
+    }
+
+    #[test]
+    fn wrong_token_is_rejected() {
+        assert!(!is_authorized(&tokens(&["secret"]), Some("Bearer wrong")));
+        assert!(!is_authorized(&tokens(&["secret"]), Some("secret")));
+    }
+
+    #[test]
+    fn correct_token_is_accepted() {
+        assert!(is_authorized(&tokens(&["secret"]), Some("Bearer secret")));
+        assert!(is_authorized(&tokens(&["a", "secret", "b"]), Some("Bearer secret")));
+    }
+}
@@ -0,0 +1,155 @@
+use std::sync::{Arc, Mutex};
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use crate::error::AppError;
+
+// ================== SQLite-backed System of Record ====================
+// A single shared connection behind a mutex is enough for this workload
+// (low write volume, SQLite serializes writers anyway); no pool is needed.
+pub type Db = Arc<Mutex<Connection>>;
+
+pub fn init_db(path: &str) -> rusqlite::Result<Db> {
+    let conn = Connection::open(path)?;
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS jobs (
+            id              TEXT PRIMARY KEY,
+            title           TEXT NOT NULL,
+            description     TEXT NOT NULL,
+            required_skills TEXT NOT NULL DEFAULT ''
+        );
+        CREATE TABLE IF NOT EXISTS candidates (
+            id               TEXT PRIMARY KEY,
+            name             TEXT,
+            raw_text         TEXT NOT NULL,
+            extracted_skills TEXT NOT NULL DEFAULT ''
+        );
+        CREATE TABLE IF NOT EXISTS match_results (
+            candidate_id    TEXT NOT NULL,
+            job_id          TEXT NOT NULL,
+            score           REAL NOT NULL,
+            matched_skills  TEXT NOT NULL DEFAULT '',
+            created_at      TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        ",
+    )?;
+    Ok(Arc::new(Mutex::new(conn)))
+}
+
+#[derive(Serialize)]
+pub struct JobRecord {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub required_skills: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct MatchRecord {
+    pub job_id: String,
+    pub score: f64,
+    pub matched_skills: Vec<String>,
+    pub created_at: String,
+}
+
+fn join_skills(skills: &[String]) -> String {
+    skills.join(",")
+}
+
+fn split_skills(s: &str) -> Vec<String> {
+    if s.is_empty() {
+        vec![]
+    } else {
+        s.split(',').map(|s| s.to_string()).collect()
+    }
+}
+
+pub fn upsert_job(
+    db: &Db,
+    id: &str,
+    title: &str,
+    description: &str,
+    required_skills: &[String],
+) -> Result<(), AppError> {
+    let conn = db.lock().unwrap();
+    conn.execute(
+        "INSERT INTO jobs (id, title, description, required_skills)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(id) DO UPDATE SET
+            title = excluded.title,
+            description = excluded.description,
+            required_skills = excluded.required_skills",
+        params![id, title, description, join_skills(required_skills)],
+    )?;
+    Ok(())
+}
+
+pub fn list_jobs(db: &Db) -> Result<Vec<JobRecord>, AppError> {
+    let conn = db.lock().unwrap();
+    let mut stmt = conn.prepare("SELECT id, title, description, required_skills FROM jobs")?;
+    let rows = stmt.query_map([], |row| {
+        Ok(JobRecord {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            description: row.get(2)?,
+            required_skills: split_skills(&row.get::<_, String>(3)?),
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+}
+
+pub fn upsert_candidate(
+    db: &Db,
+    id: &str,
+    name: Option<&str>,
+    raw_text: &str,
+    extracted_skills: &[String],
+) -> Result<(), AppError> {
+    let conn = db.lock().unwrap();
+    conn.execute(
+        "INSERT INTO candidates (id, name, raw_text, extracted_skills)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(id) DO UPDATE SET
+            name = excluded.name,
+            raw_text = excluded.raw_text,
+            extracted_skills = excluded.extracted_skills",
+        params![id, name, raw_text, join_skills(extracted_skills)],
+    )?;
+    Ok(())
+}
+
+pub fn record_match_result(
+    db: &Db,
+    candidate_id: &str,
+    job_id: &str,
+    score: f64,
+    matched_skills: &[String],
+) -> Result<(), AppError> {
+    let conn = db.lock().unwrap();
+    conn.execute(
+        "INSERT INTO match_results (candidate_id, job_id, score, matched_skills)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![candidate_id, job_id, score, join_skills(matched_skills)],
+    )?;
+    Ok(())
+}
+
+pub fn candidate_matches(db: &Db, candidate_id: &str) -> Result<Vec<MatchRecord>, AppError> {
+    let conn = db.lock().unwrap();
+    let mut stmt = conn.prepare(
+        "SELECT job_id, score, matched_skills, created_at
+         FROM match_results WHERE candidate_id = ?1
+         ORDER BY created_at DESC",
+    )?;
+    let rows = stmt.query_map(params![candidate_id], |row| {
+        Ok(MatchRecord {
+            job_id: row.get(0)?,
+            score: row.get(1)?,
+            matched_skills: split_skills(&row.get::<_, String>(2)?),
+            created_at: row.get(3)?,
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+}
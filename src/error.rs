@@ -0,0 +1,49 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use thiserror::Error;
+
+// ================== Crate-wide Error Type ====================
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error("multipart error: {0}")]
+    Multipart(#[from] axum::extract::multipart::MultipartError),
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to extract text from document: {0}")]
+    PdfExtraction(String),
+
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("database error: {0}")]
+    Db(#[from] rusqlite::Error),
+
+    #[error("missing or invalid bearer token")]
+    Unauthorized,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            AppError::Multipart(_) => StatusCode::BAD_REQUEST,
+            AppError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::PdfExtraction(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::Db(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::Unauthorized => StatusCode::UNAUTHORIZED,
+        };
+
+        (status, Json(ErrorBody { error: self.to_string() })).into_response()
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::{extract::Path, Json};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::{compute_matches, error::AppError, MatchRequest, MatchResult};
+
+// ================== Async Job Subsystem ====================
+// Tracks in-flight `/match` jobs by UUID so clients can submit a batch and
+// poll for the result instead of holding the request open. Mirrors a
+// task-waiter: each job is either a live `JoinHandle` being awaited, or the
+// finished/errored outcome, at which point it is handed back once and
+// evicted.
+struct RunningJob {
+    handle: JoinHandle<Result<Vec<MatchResult>, AppError>>,
+    started: Arc<AtomicBool>,
+}
+
+enum JobState {
+    Running(RunningJob),
+    Finished(Vec<MatchResult>, Instant),
+    Failed(String, Instant),
+}
+
+static JOBS: Lazy<Mutex<HashMap<Uuid, JobState>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+const REAP_INTERVAL: Duration = Duration::from_secs(5);
+
+// A result a client never polls for shouldn't sit in memory forever either;
+// give it a grace period to be picked up, then drop it.
+const RESULT_TTL: Duration = Duration::from_secs(600);
+
+// Moves any `Running` job whose handle has already finished into
+// `Finished`/`Failed` so a job a client never polls doesn't hold its
+// `JoinHandle` (and the task behind it) alive forever, then sweeps out
+// `Finished`/`Failed` entries older than `RESULT_TTL`.
+pub fn spawn_reaper() {
+    tokio::spawn(async {
+        loop {
+            tokio::time::sleep(REAP_INTERVAL).await;
+            reap_finished().await;
+            sweep_expired();
+        }
+    });
+}
+
+async fn reap_finished() {
+    let finished_ids: Vec<Uuid> = {
+        let jobs = JOBS.lock().unwrap();
+        jobs.iter()
+            .filter_map(|(id, state)| match state {
+                JobState::Running(job) if job.handle.is_finished() => Some(*id),
+                _ => None,
+            })
+            .collect()
+    };
+
+    for id in finished_ids {
+        let job = {
+            let mut jobs = JOBS.lock().unwrap();
+            match jobs.remove(&id) {
+                Some(JobState::Running(job)) => job,
+                // Already resolved by a concurrent poll; put whatever's
+                // there back and move on.
+                Some(other) => {
+                    jobs.insert(id, other);
+                    continue;
+                }
+                None => continue,
+            }
+        };
+
+        let state = match job.handle.await {
+            Ok(Ok(result)) => JobState::Finished(result, Instant::now()),
+            Ok(Err(app_err)) => JobState::Failed(app_err.to_string(), Instant::now()),
+            Err(join_err) => JobState::Failed(join_err.to_string(), Instant::now()),
+        };
+        JOBS.lock().unwrap().insert(id, state);
+    }
+}
+
+fn sweep_expired() {
+    let mut jobs = JOBS.lock().unwrap();
+    jobs.retain(|_, state| match state {
+        JobState::Finished(_, at) | JobState::Failed(_, at) => at.elapsed() < RESULT_TTL,
+        JobState::Running(_) => true,
+    });
+}
+
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+enum JobStatusResponse {
+    Pending,
+    Running,
+    Finished { result: Vec<MatchResult> },
+    Failed { error: String },
+}
+
+pub async fn handle_match_async(Json(payload): Json<MatchRequest>) -> Json<Uuid> {
+    let id = Uuid::new_v4();
+    let started = Arc::new(AtomicBool::new(false));
+    let started_flag = started.clone();
+
+    let handle = tokio::spawn(async move {
+        started_flag.store(true, Ordering::SeqCst);
+        compute_matches(payload)
+    });
+
+    JOBS.lock()
+        .unwrap()
+        .insert(id, JobState::Running(RunningJob { handle, started }));
+
+    Json(id)
+}
+
+pub async fn handle_match_status(Path(id): Path<Uuid>) -> Result<Json<JobStatusResponse>, AppError> {
+    // Take ownership of the entry so a finished handle can be awaited
+    // without holding the mutex across an `.await`.
+    let state = {
+        let mut jobs = JOBS.lock().unwrap();
+        jobs.remove(&id)
+    };
+
+    let state = match state {
+        Some(state) => state,
+        None => return Err(AppError::BadRequest(format!("no such job: {id}"))),
+    };
+
+    match state {
+        JobState::Running(job) => {
+            if job.handle.is_finished() {
+                return match job.handle.await {
+                    Ok(Ok(result)) => Ok(Json(JobStatusResponse::Finished { result })),
+                    Ok(Err(app_err)) => Ok(Json(JobStatusResponse::Failed { error: app_err.to_string() })),
+                    Err(join_err) => Ok(Json(JobStatusResponse::Failed { error: join_err.to_string() })),
+                };
+            }
+            let status = if job.started.load(Ordering::SeqCst) {
+                JobStatusResponse::Running
+            } else {
+                JobStatusResponse::Pending
+            };
+            JOBS.lock().unwrap().insert(id, JobState::Running(job));
+            Ok(Json(status))
+        }
+        JobState::Finished(result, _) => Ok(Json(JobStatusResponse::Finished { result })),
+        JobState::Failed(error, _) => Ok(Json(JobStatusResponse::Failed { error })),
+    }
+}
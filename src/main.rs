@@ -1,16 +1,28 @@
 use axum::{
-    extract::{Json, Multipart},
-    routing::post,
+    extract::{Extension, Json, Multipart, Path},
+    middleware,
+    routing::{get, post},
     Router,
 };
 use axum::serve;
+use axum_server::tls_rustls::RustlsConfig;
 use tokio::net::TcpListener;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashSet, net::SocketAddr, process::Command, fs};
+use std::{collections::HashSet, env, net::SocketAddr, process::Command, fs};
 use regex::Regex;
 use once_cell::sync::Lazy;
 use anyhow::Result;
 use std::path::PathBuf;
+use uuid::Uuid;
+
+mod auth;
+mod dbctx;
+mod error;
+mod jobs;
+mod scoring;
+mod worker;
+use dbctx::Db;
+use error::AppError;
 
 // ================== Skill Extraction ====================
 static SKILL_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
@@ -26,13 +38,14 @@ static SKILL_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
 });
 
 // ================== Data Models ====================
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Clone)]
 struct CandidateInput {
+    id: Option<String>,
     name: Option<String>,
     raw_text: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Clone)]
 struct JobInput {
     id: String,
     title: String,
@@ -40,19 +53,20 @@ struct JobInput {
     required_skills: Option<Vec<String>>,
 }
 
-#[derive(Serialize)]
-struct MatchResult {
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MatchResult {
     job_id: String,
     score: f64,
     matched_skills: Vec<String>,
     explanation: String,
 }
 
-#[derive(Deserialize)]
-struct MatchRequest {
+#[derive(Deserialize, Serialize, Clone)]
+pub struct MatchRequest {
     candidate: CandidateInput,
     jobs: Vec<JobInput>,
     top_k: Option<usize>,
+    scoring_script: Option<String>,
 }
 
 // ================== Core Functions ====================
@@ -81,10 +95,24 @@ fn jaccard_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
     }
 }
 
-// ================== Handlers ====================
-async fn handle_match(
-    Json(payload): Json<MatchRequest>,
-) -> Result<Json<Vec<MatchResult>>, (axum::http::StatusCode, String)> {
+// Resolves the Lua scoring script source, if any: an inline script on the
+// request takes precedence over the `SCORING_SCRIPT` env var, which names a
+// file on disk. Absence of both falls back to the built-in weighted formula.
+fn load_scoring_script(payload: &MatchRequest) -> Result<Option<String>, AppError> {
+    if let Some(script) = &payload.scoring_script {
+        return Ok(Some(script.clone()));
+    }
+    if let Ok(path) = env::var("SCORING_SCRIPT") {
+        let script = fs::read_to_string(&path)?;
+        return Ok(Some(script));
+    }
+    Ok(None)
+}
+
+// Scores a candidate against every job in the request. Pulled out of
+// `handle_match` so the async job subsystem can run it on a spawned task.
+pub fn compute_matches(payload: MatchRequest) -> Result<Vec<MatchResult>, AppError> {
+    let script = load_scoring_script(&payload)?;
     let candidate_skills = extract_skills_from_text(&payload.candidate.raw_text);
     let cand_set: HashSet<String> = candidate_skills.iter().cloned().collect();
 
@@ -105,12 +133,20 @@ async fn handle_match(
         let skill_score = jaccard_similarity(&cand_set, &job_skills);
         let experience_score = 0.0f64;
 
-        let final_score =
-            100.0 * (0.6 * skill_score + 0.25 * experience_score + 0.15 * 0.0);
-
         let matched: Vec<String> =
             cand_set.intersection(&job_skills).cloned().collect();
 
+        let final_score = scoring::score(
+            script.as_deref(),
+            &scoring::ScoreInputs {
+                skill_score,
+                experience_score,
+                matched_count: matched.len(),
+                candidate_skill_count: cand_set.len(),
+                job_skill_count: job_skills.len(),
+            },
+        )?;
+
         let explanation = format!("skill_jaccard={:.3}", skill_score);
 
         results.push(MatchResult {
@@ -125,16 +161,85 @@ async fn handle_match(
     let top_k = payload.top_k.unwrap_or(results.len()).min(results.len());
     results.truncate(top_k);
 
+    Ok(results)
+}
+
+// ================== Handlers ====================
+async fn handle_match(
+    Extension(db): Extension<Db>,
+    Json(payload): Json<MatchRequest>,
+) -> Result<Json<Vec<MatchResult>>, AppError> {
+    let candidate_id = payload
+        .candidate
+        .id
+        .clone()
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    let candidate_name = payload.candidate.name.clone();
+    let candidate_raw_text = payload.candidate.raw_text.clone();
+    let candidate_skills = extract_skills_from_text(&candidate_raw_text);
+
+    let results = compute_matches(payload)?;
+
+    dbctx::upsert_candidate(
+        &db,
+        &candidate_id,
+        candidate_name.as_deref(),
+        &candidate_raw_text,
+        &candidate_skills,
+    )?;
+    for result in &results {
+        dbctx::record_match_result(
+            &db,
+            &candidate_id,
+            &result.job_id,
+            result.score,
+            &result.matched_skills,
+        )?;
+    }
+
     Ok(Json(results))
 }
 
-async fn handle_parse(mut multipart: Multipart) -> Result<String, (axum::http::StatusCode, String)> {
-    while let Some(field) = multipart.next_field().await.unwrap() {
+#[derive(Deserialize)]
+struct JobPostInput {
+    id: String,
+    title: String,
+    description: String,
+    required_skills: Option<Vec<String>>,
+}
+
+async fn handle_upsert_job(
+    Extension(db): Extension<Db>,
+    Json(payload): Json<JobPostInput>,
+) -> Result<Json<dbctx::JobRecord>, AppError> {
+    let required_skills = payload.required_skills.unwrap_or_default();
+    dbctx::upsert_job(&db, &payload.id, &payload.title, &payload.description, &required_skills)?;
+    Ok(Json(dbctx::JobRecord {
+        id: payload.id,
+        title: payload.title,
+        description: payload.description,
+        required_skills,
+    }))
+}
+
+async fn handle_list_jobs(Extension(db): Extension<Db>) -> Result<Json<Vec<dbctx::JobRecord>>, AppError> {
+    Ok(Json(dbctx::list_jobs(&db)?))
+}
+
+async fn handle_candidate_matches(
+    Extension(db): Extension<Db>,
+    Path(candidate_id): Path<String>,
+) -> Result<Json<Vec<dbctx::MatchRecord>>, AppError> {
+    Ok(Json(dbctx::candidate_matches(&db, &candidate_id)?))
+}
+
+async fn handle_parse(mut multipart: Multipart) -> Result<String, AppError> {
+    while let Some(field) = multipart.next_field().await? {
         let file_name = field.file_name().unwrap_or("upload").to_string();
-        let data = field.bytes().await.unwrap();
+        let data = field.bytes().await?;
 
         let path = PathBuf::from(format!("/tmp/{}", file_name));
-        fs::write(&path, &data).unwrap();
+        fs::write(&path, &data)?;
 
         let text = if file_name.ends_with(".pdf") {
             // Use `pdftotext` (must be installed: sudo apt install poppler-utils)
@@ -142,29 +247,101 @@ async fn handle_parse(mut multipart: Multipart) -> Result<String, (axum::http::S
                 .arg(&path)
                 .arg("-") // output to stdout
                 .output()
-                .unwrap();
+                .map_err(|e| AppError::PdfExtraction(e.to_string()))?;
+            if !output.status.success() {
+                return Err(AppError::PdfExtraction(
+                    String::from_utf8_lossy(&output.stderr).to_string(),
+                ));
+            }
             String::from_utf8_lossy(&output.stdout).to_string()
         } else {
-            // Assume plain text
+            // Anything else (.txt, .md, no extension, ...) is treated as
+            // plain text rather than rejected.
             String::from_utf8_lossy(&data).to_string()
         };
 
         return Ok(text);
     }
-    Err((axum::http::StatusCode::BAD_REQUEST, "No file uploaded".into()))
+    Err(AppError::BadRequest("No file uploaded".into()))
+}
+
+async fn handle_health() -> &'static str {
+    "ok"
 }
 
 // ================== Main ====================
 #[tokio::main]
 async fn main() {
-    let app = Router::new()
+    // `<binary> --runner [driver-url]` runs this process as a distributed
+    // worker instead of the driver, long-polling the driver's /work queue.
+    let args: Vec<String> = env::args().collect();
+    if args.iter().any(|a| a == "--runner") {
+        let base_url = args
+            .iter()
+            .position(|a| a == "--runner")
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+            .unwrap_or_else(|| "http://127.0.0.1:8081".to_string());
+        let token = auth::runner_token();
+        if token.is_none() {
+            println!("Warning: no RUNNER_TOKEN/AUTH_TOKENS configured; requests to an auth-gated driver will fail with 401");
+        }
+        println!("Running as a distributed worker against {base_url}");
+        worker::RunnerClient::new(base_url, token).run().await;
+    }
+
+    jobs::spawn_reaper();
+    worker::spawn_sweeper();
+
+    let db_path = env::var("MATCHER_DB").unwrap_or_else(|_| "matcher.sqlite".to_string());
+    let db = dbctx::init_db(&db_path).expect("failed to initialize sqlite database");
+
+    // `/health` stays public (no bearer token required) so uptime probes and
+    // load balancers don't need a credential; every other route sits behind
+    // the auth layer.
+    let public = Router::new().route("/health", get(handle_health));
+
+    let protected = Router::new()
         .route("/match", post(handle_match))
-        .route("/parse", post(handle_parse));
+        .route("/parse", post(handle_parse))
+        .route("/match/async", post(jobs::handle_match_async))
+        .route("/match/status/:id", get(jobs::handle_match_status))
+        .route("/jobs", post(handle_upsert_job).get(handle_list_jobs))
+        .route("/candidates/:id/matches", get(handle_candidate_matches))
+        .route("/match/distributed", post(worker::handle_match_distributed))
+        .route("/match/distributed/status/:id", get(worker::handle_distributed_status))
+        .route("/work", get(worker::handle_get_work))
+        .route("/work/:id/result", post(worker::handle_post_work_result))
+        .route_layer(middleware::from_fn(auth::require_bearer_token));
+
+    let app = public.merge(protected).layer(Extension(db));
 
     let addr = SocketAddr::from(([127, 0, 0, 1], 8081));
-    println!("Resume matcher listening on {}", addr);
 
-    let listener = TcpListener::bind(addr).await.unwrap();
-    serve(listener, app).await.unwrap();
+    match (env::var("TLS_CERT"), env::var("TLS_KEY")) {
+        (Ok(cert_path), Ok(key_path)) => {
+            println!("Resume matcher listening on {} (TLS)", addr);
+            let config = RustlsConfig::from_pem_file(&cert_path, &key_path)
+                .await
+                .unwrap_or_else(|e| {
+                    panic!("failed to load TLS cert/key ({}, {}): {}", cert_path, key_path, e)
+                });
+            axum_server::bind_rustls(addr, config)
+                .serve(app.into_make_service())
+                .await
+                .unwrap();
+        }
+        (Ok(_), Err(_)) => {
+            panic!("TLS_CERT is set but TLS_KEY is not; refusing to fall back to plain HTTP for a service handling resume PII");
+        }
+        (Err(_), Ok(_)) => {
+            panic!("TLS_KEY is set but TLS_CERT is not; refusing to fall back to plain HTTP for a service handling resume PII");
+        }
+        (Err(_), Err(_)) => {
+            println!("Resume matcher listening on {} (plain HTTP)", addr);
+            let listener = TcpListener::bind(addr).await.unwrap();
+            serve(listener, app).await.unwrap();
+        }
+    }
 }
 
@@ -0,0 +1,134 @@
+use std::time::{Duration, Instant};
+
+use mlua::{HookTriggers, Lua, StdLib};
+
+use crate::error::AppError;
+
+// ================== Pluggable Scoring ====================
+// Lets operators override the weighted scoring formula with a Lua
+// `score()` function instead of recompiling. The Lua state is sandboxed
+// (no `os`/`io`) since the script is untrusted input.
+const SCRIPT_TIMEOUT: Duration = Duration::from_millis(500);
+
+pub struct ScoreInputs {
+    pub skill_score: f64,
+    pub experience_score: f64,
+    pub matched_count: usize,
+    pub candidate_skill_count: usize,
+    pub job_skill_count: usize,
+}
+
+fn default_formula(inputs: &ScoreInputs) -> f64 {
+    100.0 * (0.6 * inputs.skill_score + 0.25 * inputs.experience_score + 0.15 * 0.0)
+}
+
+pub fn score(script: Option<&str>, inputs: &ScoreInputs) -> Result<f64, AppError> {
+    let Some(script) = script else {
+        return Ok(default_formula(inputs));
+    };
+
+    // Load only BASE/TABLE/STRING/MATH so scripts can do arithmetic and
+    // string formatting but can't touch the filesystem or spawn processes.
+    let sandbox = StdLib::BASE | StdLib::TABLE | StdLib::STRING | StdLib::MATH;
+    let lua = Lua::new_with(sandbox, mlua::LuaOptions::default())
+        .map_err(|e| AppError::BadRequest(format!("failed to init Lua sandbox: {e}")))?;
+
+    let globals = lua.globals();
+    // BASE still exposes `dofile`/`loadfile`/`load`/`require`, which can read
+    // arbitrary files off the host (or execute attacker-supplied bytecode) even
+    // with io/os excluded from `StdLib`. Strip them explicitly.
+    for unsafe_global in ["dofile", "loadfile", "load", "require"] {
+        globals
+            .set(unsafe_global, mlua::Value::Nil)
+            .map_err(|e| AppError::BadRequest(format!("failed to sandbox Lua globals: {e}")))?;
+    }
+
+    // A script that loops forever (or just runs long) would otherwise pin
+    // whatever thread calls `score()` indefinitely. Abort it once it's had
+    // `SCRIPT_TIMEOUT` of wall-clock time, checked every 1000 VM instructions.
+    let deadline = Instant::now() + SCRIPT_TIMEOUT;
+    let triggers = HookTriggers { every_nth_instruction: Some(1000), ..Default::default() };
+    lua.set_hook(triggers, move |_lua, _debug| {
+        if Instant::now() >= deadline {
+            Err(mlua::Error::RuntimeError(
+                "scoring script exceeded its execution time limit".into(),
+            ))
+        } else {
+            Ok(())
+        }
+    })
+    .map_err(|e| AppError::BadRequest(format!("failed to install scoring script timeout: {e}")))?;
+
+    globals
+        .set("skill_score", inputs.skill_score)
+        .and_then(|_| globals.set("experience_score", inputs.experience_score))
+        .and_then(|_| globals.set("matched_count", inputs.matched_count as i64))
+        .and_then(|_| globals.set("candidate_skill_count", inputs.candidate_skill_count as i64))
+        .and_then(|_| globals.set("job_skill_count", inputs.job_skill_count as i64))
+        .map_err(|e| AppError::BadRequest(format!("failed to set scoring globals: {e}")))?;
+
+    lua.load(script)
+        .exec()
+        .map_err(|e| AppError::BadRequest(format!("scoring script error: {e}")))?;
+
+    let score_fn: mlua::Function = globals
+        .get("score")
+        .map_err(|_| AppError::BadRequest("scoring script must define a score() function".into()))?;
+
+    score_fn
+        .call::<f64>(())
+        .map_err(|e| AppError::BadRequest(format!("scoring script error: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs() -> ScoreInputs {
+        ScoreInputs {
+            skill_score: 0.5,
+            experience_score: 0.2,
+            matched_count: 2,
+            candidate_skill_count: 4,
+            job_skill_count: 4,
+        }
+    }
+
+    #[test]
+    fn no_script_uses_default_formula() {
+        let result = score(None, &inputs()).unwrap();
+        assert_eq!(result, default_formula(&inputs()));
+    }
+
+    #[test]
+    fn script_can_read_injected_globals() {
+        let result = score(Some("function score() return skill_score + matched_count end"), &inputs()).unwrap();
+        assert_eq!(result, 0.5 + 2.0);
+    }
+
+    #[test]
+    fn sandbox_strips_dofile_loadfile_load_require() {
+        let err = score(
+            Some("function score() dofile('/etc/passwd'); return 0 end"),
+            &inputs(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("scoring script error"));
+
+        for unsafe_global in ["loadfile", "load", "require"] {
+            let script = format!("function score() {unsafe_global}('x'); return 0 end");
+            let err = score(Some(&script), &inputs()).unwrap_err();
+            assert!(err.to_string().contains("scoring script error"));
+        }
+    }
+
+    #[test]
+    fn infinite_loop_script_is_aborted_by_timeout() {
+        let started = Instant::now();
+        let err = score(Some("function score() while true do end end"), &inputs()).unwrap_err();
+        assert!(err.to_string().contains("execution time limit"));
+        // Generous upper bound so this doesn't flake under load while still
+        // proving the hook aborted the script instead of hanging the test.
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use axum::{extract::Path, http::StatusCode, Json};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{error::AppError, MatchRequest, MatchResult};
+
+// ================== Distributed Worker Protocol ====================
+// Splits matching into this driver and a pool of `RunnerClient` processes
+// that claim work over HTTP, mirroring a CI runner/driver split: the driver
+// tracks each task's lifecycle by UUID and reclaims a task back to Pending
+// if the runner holding it doesn't submit a result before its lease expires.
+const LEASE: Duration = Duration::from_secs(30);
+const LONG_POLL: Duration = Duration::from_secs(20);
+const SWEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+// A `Done` task a driver client never polls for shouldn't sit in the queue
+// forever either; give it a grace period to be picked up, then drop it.
+const RESULT_TTL: Duration = Duration::from_secs(600);
+
+enum TaskState {
+    Pending,
+    Running { lease_expires: Instant },
+    Done(Vec<MatchResult>, Instant),
+}
+
+struct WorkItem {
+    payload: MatchRequest,
+    state: TaskState,
+}
+
+static QUEUE: Lazy<Mutex<HashMap<Uuid, WorkItem>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Periodically drops `Done` tasks older than `RESULT_TTL` so a driver client
+// that never polls `/match/distributed/status/:id` doesn't leak queue entries.
+pub fn spawn_sweeper() {
+    tokio::spawn(async {
+        loop {
+            tokio::time::sleep(SWEEP_INTERVAL).await;
+            let mut queue = QUEUE.lock().unwrap();
+            queue.retain(|_, item| match &item.state {
+                TaskState::Done(_, at) => at.elapsed() < RESULT_TTL,
+                _ => true,
+            });
+        }
+    });
+}
+
+pub fn enqueue(payload: MatchRequest) -> Uuid {
+    let id = Uuid::new_v4();
+    QUEUE
+        .lock()
+        .unwrap()
+        .insert(id, WorkItem { payload, state: TaskState::Pending });
+    id
+}
+
+// Reclaims any task whose lease expired (its runner died mid-task) and
+// claims the next `Pending` one, flipping it to `Running` with a fresh lease.
+fn claim_next() -> Option<(Uuid, MatchRequest)> {
+    let mut queue = QUEUE.lock().unwrap();
+    let now = Instant::now();
+
+    for item in queue.values_mut() {
+        if let TaskState::Running { lease_expires } = item.state {
+            if now >= lease_expires {
+                item.state = TaskState::Pending;
+            }
+        }
+    }
+
+    let next_id = queue
+        .iter()
+        .find(|(_, item)| matches!(item.state, TaskState::Pending))
+        .map(|(id, _)| *id)?;
+
+    let item = queue.get_mut(&next_id).unwrap();
+    item.state = TaskState::Running { lease_expires: now + LEASE };
+    Some((next_id, item.payload.clone()))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WorkTask {
+    pub id: Uuid,
+    #[serde(flatten)]
+    pub payload: MatchRequest,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+enum DistributedStatusResponse {
+    Pending,
+    Running,
+    Done { result: Vec<MatchResult> },
+}
+
+pub async fn handle_match_distributed(Json(payload): Json<MatchRequest>) -> Json<Uuid> {
+    Json(enqueue(payload))
+}
+
+pub async fn handle_distributed_status(
+    Path(id): Path<Uuid>,
+) -> Result<Json<DistributedStatusResponse>, AppError> {
+    let mut queue = QUEUE.lock().unwrap();
+    match queue.get(&id) {
+        Some(item) => match &item.state {
+            TaskState::Pending => Ok(Json(DistributedStatusResponse::Pending)),
+            TaskState::Running { .. } => Ok(Json(DistributedStatusResponse::Running)),
+            TaskState::Done(..) => {
+                let item = queue.remove(&id).unwrap();
+                let TaskState::Done(result, _) = item.state else { unreachable!() };
+                Ok(Json(DistributedStatusResponse::Done { result }))
+            }
+        },
+        None => Err(AppError::BadRequest(format!("no such task: {id}"))),
+    }
+}
+
+// Long-polls for the next `Pending` task, holding the connection open until
+// one is claimed or `LONG_POLL` elapses (then `204 No Content`).
+pub async fn handle_get_work() -> Result<Json<WorkTask>, StatusCode> {
+    let deadline = Instant::now() + LONG_POLL;
+    loop {
+        if let Some((id, payload)) = claim_next() {
+            return Ok(Json(WorkTask { id, payload }));
+        }
+        if Instant::now() >= deadline {
+            return Err(StatusCode::NO_CONTENT);
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+pub async fn handle_post_work_result(
+    Path(id): Path<Uuid>,
+    Json(results): Json<Vec<MatchResult>>,
+) -> Result<StatusCode, AppError> {
+    let mut queue = QUEUE.lock().unwrap();
+    match queue.get_mut(&id) {
+        Some(item) => {
+            item.state = TaskState::Done(results, Instant::now());
+            Ok(StatusCode::NO_CONTENT)
+        }
+        None => Err(AppError::BadRequest(format!("no such task (unknown, or its result was already collected or expired): {id}"))),
+    }
+}
+
+// ================== Runner ====================
+// A standalone worker loop: long-polls the driver for work, scores it with
+// `compute_matches` (the same function `/match` uses), and posts the result
+// back. Run via `<binary> --runner [driver-url]`.
+pub struct RunnerClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl RunnerClient {
+    pub fn new(base_url: impl Into<String>, token: Option<String>) -> Self {
+        Self { http: reqwest::Client::new(), base_url: base_url.into(), token }
+    }
+
+    fn authorize(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    pub async fn run(&self) -> ! {
+        const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+        const MAX_BACKOFF: Duration = Duration::from_secs(30);
+        let mut backoff = INITIAL_BACKOFF;
+
+        loop {
+            match self.claim_and_process().await {
+                Ok(_) => backoff = INITIAL_BACKOFF,
+                Err(e) => {
+                    eprintln!("runner: {e}");
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+    }
+
+    async fn claim_and_process(&self) -> anyhow::Result<bool> {
+        let resp = self
+            .authorize(self.http.get(format!("{}/work", self.base_url)))
+            .send()
+            .await?;
+        if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
+            anyhow::bail!("driver rejected runner credentials (401) — check RUNNER_TOKEN/AUTH_TOKENS");
+        }
+        if resp.status() == reqwest::StatusCode::NO_CONTENT {
+            return Ok(false);
+        }
+        let task: WorkTask = resp.json().await?;
+        // Share the exact same scoring path `/match` uses (skill weighting,
+        // any `scoring_script`, sorting, `top_k`) so a result doesn't depend
+        // on whether the task happened to run locally or on a runner.
+        let results = crate::compute_matches(task.payload)?;
+
+        self.authorize(self.http.post(format!("{}/work/{}/result", self.base_url, task.id)))
+            .json(&results)
+            .send()
+            .await?;
+        Ok(true)
+    }
+}